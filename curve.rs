@@ -0,0 +1,104 @@
+use num_bigint::BigInt;
+use num_traits::{pow, Zero};
+use crate::curve_element::{self, CurveElement};
+use crate::point::Point;
+
+// Parameters describing a short Weierstrass curve y^2 = x^3 + ax + b over
+// F_p, together with its generator point, group order and cofactor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Curve {
+    pub a: i32,
+    pub b: i32,
+    pub p: BigInt,
+    pub gx: BigInt,
+    pub gy: BigInt,
+    pub n: BigInt,
+    pub h: u32,
+}
+
+impl Curve {
+
+    // Construct a curve descriptor, rejecting degenerate curves where
+    // 4a^3 + 27b^2 ≡ 0 (mod p), i.e. the equation has a repeated root and
+    // the point set doesn't form a group.
+    pub fn new(a: i32, b: i32, p: BigInt, gx: BigInt, gy: BigInt, n: BigInt, h: u32) -> Curve {
+        let discriminant = curve_element::modulo(
+            &(BigInt::from(4) * pow(BigInt::from(a), 3) + BigInt::from(27) * pow(BigInt::from(b), 2)),
+            &p,
+        );
+
+        if discriminant.is_zero() {
+            panic!("curve a={} b={} is degenerate: 4a^3 + 27b^2 \u{2261} 0 (mod p)", a, b);
+        }
+
+        Curve{a, b, p, gx, gy, n, h}
+    }
+
+    // The curve's base point G
+    pub fn generator(&self) -> Point {
+        Point::new(CurveElement::Some(self.gx.clone()), CurveElement::Some(self.gy.clone()), self.clone())
+    }
+
+    // Whether `point` satisfies this curve's equation over this curve's field
+    pub fn contains(&self, point: &Point) -> bool {
+        if point.x.is_none() {
+            return true;
+        }
+
+        if point.curve != *self {
+            return false;
+        }
+
+        let x = point.x.unwrap();
+        let y = point.y.unwrap();
+        let lhs = curve_element::modulo(&pow(y.clone(), 2), &self.p);
+        let rhs = curve_element::modulo(&(pow(x.clone(), 3) + BigInt::from(self.a) * x + BigInt::from(self.b)), &self.p);
+        lhs == rhs
+    }
+}
+
+// secp256k1, as standardized in SEC 2
+pub fn secp256k1() -> Curve {
+    let p = pow(BigInt::from(2), 256) - pow(BigInt::from(2), 32) - BigInt::from(977);
+
+    Curve::new(
+        0,
+        7,
+        p,
+        BigInt::parse_bytes(b"79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798", 16).unwrap(),
+        BigInt::parse_bytes(b"483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8", 16).unwrap(),
+        BigInt::parse_bytes(b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141", 16).unwrap(),
+        1,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secp256k1_generator_is_on_curve() {
+        let curve = secp256k1();
+        let g = curve.generator();
+        assert!(curve.contains(&g));
+    }
+
+    #[test]
+    fn contains_accepts_infinity_and_rejects_off_curve_points() {
+        let curve = secp256k1();
+
+        let inf = Point::new(CurveElement::None, CurveElement::None, curve.clone());
+        assert!(curve.contains(&inf));
+
+        let i2c = CurveElement::int_to_curve;
+        let off_curve = Point{x: i2c(1), y: i2c(1), curve: curve.clone()};
+        assert!(!curve.contains(&off_curve));
+    }
+
+    #[test]
+    #[should_panic(expected = "degenerate")]
+    fn degenerate_curve_is_rejected() {
+        // a=0, b=0 gives 4*0 + 27*0 == 0 (mod p) for any p
+        Curve::new(0, 0, BigInt::from(1_000_000_007i64), BigInt::from(0), BigInt::from(0), BigInt::from(1), 1);
+    }
+}
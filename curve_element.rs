@@ -0,0 +1,60 @@
+use std::fmt;
+use num_bigint::BigInt;
+use num_traits::{Zero, One};
+
+// A single coordinate of a Point, living in F_p.
+// None represents the point at infinity's placeholder coordinate.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CurveElement {
+    None,
+    Some(BigInt),
+}
+
+impl CurveElement {
+
+    // Wrap a plain integer as a curve element
+    pub fn int_to_curve(x: i64) -> CurveElement {
+        CurveElement::Some(BigInt::from(x))
+    }
+
+    pub fn is_none(&self) -> bool {
+        matches!(self, CurveElement::None)
+    }
+
+    pub fn unwrap(&self) -> &BigInt {
+        match self {
+            CurveElement::Some(x) => x,
+            CurveElement::None => panic!("called `unwrap()` on a `None` value"),
+        }
+    }
+}
+
+impl fmt::Display for CurveElement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CurveElement::None => write!(f, "None"),
+            CurveElement::Some(x) => write!(f, "{}", x),
+        }
+    }
+}
+
+// Reduce `a` into the range [0, p) regardless of sign
+pub fn modulo(a: &BigInt, p: &BigInt) -> BigInt {
+    ((a % p) + p) % p
+}
+
+// Extended Euclidean algorithm: returns (gcd, x, y) such that a*x + b*y = gcd
+pub fn egcd(a: &BigInt, b: &BigInt) -> (BigInt, BigInt, BigInt) {
+    if a.is_zero() {
+        (b.clone(), BigInt::zero(), BigInt::one())
+    } else {
+        let (g, x1, y1) = egcd(&modulo(b, a), a);
+        (g, y1 - (b / a) * &x1, x1)
+    }
+}
+
+// Modular inverse of `a` modulo prime `p`, via the extended Euclidean algorithm
+pub fn mod_inverse(a: &BigInt, p: &BigInt) -> BigInt {
+    let (_, x, _) = egcd(&modulo(a, p), p);
+    modulo(&x, p)
+}
@@ -0,0 +1,60 @@
+use num_bigint::BigInt;
+use crate::curve;
+use crate::point::Point;
+
+// Derive the secp256k1 public point for a private key: private * G
+pub fn keypair(private: BigInt) -> (BigInt, Point) {
+    let public = curve::secp256k1().generator() * private.clone();
+    (private, public)
+}
+
+// Compute the ECDH shared secret point: private * peer_public.
+// Rejects an off-curve or point-at-infinity peer key before multiplying,
+// since accepting either is a classic small-subgroup attack vector.
+pub fn ecdh_shared(private: BigInt, peer_public: Point) -> Point {
+    let curve = curve::secp256k1();
+
+    if peer_public.x.is_none() {
+        panic!("peer public key is the point at infinity");
+    }
+
+    if !curve.contains(&peer_public) {
+        panic!("peer public key is not on the curve");
+    }
+
+    peer_public * private
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve_element::CurveElement;
+
+    #[test]
+    fn shared_secret_matches_both_directions() {
+        let (alice_priv, alice_pub) = keypair(BigInt::from(12345));
+        let (bob_priv, bob_pub) = keypair(BigInt::from(67890));
+
+        let alice_shared = ecdh_shared(alice_priv, bob_pub);
+        let bob_shared = ecdh_shared(bob_priv, alice_pub);
+
+        assert_eq!(alice_shared, bob_shared);
+    }
+
+    #[test]
+    #[should_panic(expected = "point at infinity")]
+    fn rejects_infinity_peer_key() {
+        let curve = curve::secp256k1();
+        let inf = Point::new(CurveElement::None, CurveElement::None, curve);
+        ecdh_shared(BigInt::from(1), inf);
+    }
+
+    #[test]
+    #[should_panic(expected = "not on the curve")]
+    fn rejects_off_curve_peer_key() {
+        let curve = curve::secp256k1();
+        let i2c = CurveElement::int_to_curve;
+        let off_curve = Point{x: i2c(1), y: i2c(1), curve};
+        ecdh_shared(BigInt::from(1), off_curve);
+    }
+}
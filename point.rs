@@ -1,175 +1,459 @@
-use std::ops::Add;
-use std::ops::Mul;
-use num_traits::pow;
-use crate::curve_element::CurveElement;
-
-#[derive(Debug, Clone, Copy)]
-pub struct Point {
-    pub x: CurveElement,
-    pub y: CurveElement,
-    pub a: i32,
-    pub b: i32
-}
-
-impl Point {
-
-    // Setup new point
-    pub fn new(x: CurveElement, y: CurveElement, a: i32, b: i32) -> Point {
-
-        // x being None and y being None represents the point at infinity
-        // Check for that here since the equation below won't make sense
-        // with None values for both.
-         
-        if x.is_none() & y.is_none() {
-            //This is the point at infinity
-            return Point{x: x, y: y, a: a, b: b};
-        }
-
-        let nx = *(x.unwrap());
-        let ny = *(y.unwrap());
-
-
-        if pow(ny, 2) != pow(nx, 3) + a * nx + b {
-            panic!("({}, {}) is not on the curve", nx, ny);
-        }
-        Point{x: x, y: y, a: a, b: b}
-    }
-
-    // View the point
-    pub fn view(&self) -> String{
-        format!("Point({},{})_{}_{}", self.x.to_string(), self.y.to_string(), self.a, self.b)
-    }
-}
-
-// Implement == for 2 finite field elements
-impl PartialEq for Point {
-    fn eq(&self, other: &Self) -> bool {
-
-        if (self.x == other.x) & (self.y == other.y) & (self.a == other.a) & (self.b == other.b) {
-            true
-        }else {
-            false
-        }
-    }
-}
-impl Eq for Point {}
-
-// Implement addition for 2 finite field elements
-impl Add for Point {
-    type Output = Self;
-
-    fn add(self, other: Self) -> Self {
-        if (self.a != other.a) | (self.b != other.b) {
-            panic!("Points {:?}, {:?} are not on the same curve", self.view(), other.view())
-        }
-
-        // Case 0.0: self is the point at infinity, return other
-        if self.x.is_none() {
-            return other;
-        }
-        
-        // Case 0.1: other is the point at infinity, return self
-        if other.x.is_none() {
-            return self;
-        }
-
-        // Case 1: self.x == other.x, self.y != other.y
-        // Result is point at infinity
-        if (self.x == other.x) & (self.y != other.y) {
-            return Point{x: CurveElement::None, y: CurveElement::None, a: self.a, b: self.b};
-        }
-
-        // Case 2: self.x ≠ other.x
-        // Formula (x3,y3)==(x1,y1)+(x2,y2)
-        // s=(y2-y1)/(x2-x1)
-        // x3=s**2-x1-x2
-        // y3=s*(x1-x3)-y1
-
-        if self.x != other.x {
-            let sx = *(self.x.unwrap());
-            let sy = *(self.y.unwrap());
-            let ox = *(other.x.unwrap());
-            let oy = *(other.y.unwrap());
-
-            let s=(oy-sy)/(ox-sx);
-            let x = pow(s, 2) - sx - ox;
-            let y = s * (sx - x) - sy;
-            return Point{x: CurveElement::int_to_curve(x), y: CurveElement::int_to_curve(y), a: self.a, b: self.b};
-        }  
-        
-        // Case 4: if we are tangent to the vertical line,
-        // we return the point at infinity
-        // note instead of figuring out what 0 is for each type
-        // we just use 0 * self.x
-
-        if (self == other) & (*(self.y.unwrap()) == 0 * *(self.x.unwrap())) {
-            return Point{x: CurveElement::None, y: CurveElement::None, a: self.a, b: self.b};
-        } 
-        
-        // Case 3: self == other
-        // Formula (x3,y3)=(x1,y1)+(x1,y1)
-        // s=(3*x1**2+a)/(2*y1)
-        // x3=s**2-2*x1
-        // y3=s*(x1-x3)-y1
-        if self == other {
-            let sx = *(self.x.unwrap());
-            let sy = *(self.y.unwrap());
-            let s = (3 * pow(sx, 2) + self.a) / (2 * sy);
-            let x = pow(s,2) - 2 * sx;
-            let y = s * (sx - x) - sy;
-            return Point{x: CurveElement::int_to_curve(x), y: CurveElement::int_to_curve(y), a: self.a, b: self.b};
-        }
-        panic!("Something wrong with the addition");
-    }
-}
-
-
-
-
-//====================================================================================================
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    
-    fn valid_point() {
-
-        let i2c = CurveElement::int_to_curve;
-
-        // On curve
-        //let _x = Point::new(-2, 4, 5, 7);
-        let _x = Point::new(i2c(3), i2c(-7), 5, 7); // should not raise an error
-        let _x = Point::new(i2c(18), i2c(77), 5, 7); // should not raise an error
-
-        // == implementation
-        let p1 = Point::new(i2c(3), i2c(-7), 5, 7);
-        let p2 = Point::new(i2c(3), i2c(-7), 5, 7);
-        let p3 = Point::new(i2c(18), i2c(77), 5, 7);
-        assert!(p1 == p2);
-        assert!(p1 != p3);
-
-        // Show point
-        assert_eq!(p1.view(), String::from("Point(3,-7)_5_7"));
-
-        // Test add 0
-        let a = Point::new(CurveElement::None, CurveElement::None, 5, 7);
-        let b = Point::new(i2c(2), i2c(5), 5, 7);
-        let c = Point::new(i2c(2), i2c(-5), 5, 7);
-        assert_eq!(a + b, b);
-        assert_eq!(b + a, b);
-        assert_eq!(b + c, a);
-
-        // Test add 1
-        let a = Point::new(i2c(3), i2c(7), 5, 7);
-        let b = Point::new(i2c(-1), i2c(-1), 5, 7);
-        assert_eq!(a + b, Point::new(i2c(2), i2c(-5), 5, 7));
-
-        // Test add2
-        let a = Point::new(i2c(-1), i2c(1), 5, 7);
-        assert_eq!(a + a, Point::new(i2c(18), i2c(-77), 5, 7));
-
-    }
-}
-
+use std::ops::Add;
+use std::ops::Mul;
+use std::ops::Neg;
+use std::ops::Sub;
+use num_bigint::{BigInt, Sign};
+use num_traits::{pow, One, Zero};
+use crate::curve::Curve;
+use crate::curve_element::{self, CurveElement};
+
+// Encode a field element as a fixed-width 32-byte big-endian buffer
+fn to_32_bytes(n: &BigInt) -> Vec<u8> {
+    let (_, mut bytes) = n.to_bytes_be();
+    while bytes.len() < 32 {
+        bytes.insert(0, 0);
+    }
+    bytes
+}
+
+#[derive(Debug, Clone)]
+pub struct Point {
+    pub x: CurveElement,
+    pub y: CurveElement,
+    pub curve: Curve
+}
+
+impl Point {
+
+    // Setup new point on `curve`'s equation y^2 = x^3 + ax + b over F_p
+    pub fn new(x: CurveElement, y: CurveElement, curve: Curve) -> Point {
+
+        // x being None and y being None represents the point at infinity
+        // Check for that here since the equation below won't make sense
+        // with None values for both.
+
+        if x.is_none() & y.is_none() {
+            //This is the point at infinity
+            return Point{x: x, y: y, curve: curve};
+        }
+
+        let nx = x.unwrap().clone();
+        let ny = y.unwrap().clone();
+
+        let lhs = curve_element::modulo(&pow(ny.clone(), 2), &curve.p);
+        let rhs = curve_element::modulo(&(pow(nx.clone(), 3) + BigInt::from(curve.a) * &nx + BigInt::from(curve.b)), &curve.p);
+
+        if lhs != rhs {
+            panic!("({}, {}) is not on the curve", nx, ny);
+        }
+
+        // Store coordinates in canonical [0, p) form so that two points
+        // constructed from congruent-but-unreduced inputs compare equal.
+        let rx = curve_element::modulo(&nx, &curve.p);
+        let ry = curve_element::modulo(&ny, &curve.p);
+        Point{x: CurveElement::Some(rx), y: CurveElement::Some(ry), curve: curve}
+    }
+
+    // View the point
+    pub fn view(&self) -> String{
+        format!("Point({},{})_{}_{}", self.x.to_string(), self.y.to_string(), self.curve.a, self.curve.b)
+    }
+
+    // SEC1 serialization of a secp256k1 public key.
+    // Uncompressed: 0x04 || x || y. Compressed: 0x02/0x03 || x, the prefix
+    // byte encoding the parity of y. The point at infinity serializes to 0x00.
+    pub fn to_sec(&self, compressed: bool) -> Vec<u8> {
+        if self.x.is_none() {
+            return vec![0x00];
+        }
+
+        let x_bytes = to_32_bytes(self.x.unwrap());
+
+        if compressed {
+            let prefix = if (self.y.unwrap() % 2) == BigInt::zero() { 0x02 } else { 0x03 };
+            let mut sec = vec![prefix];
+            sec.extend(x_bytes);
+            sec
+        } else {
+            let y_bytes = to_32_bytes(self.y.unwrap());
+            let mut sec = vec![0x04];
+            sec.extend(x_bytes);
+            sec.extend(y_bytes);
+            sec
+        }
+    }
+
+    // Parse a SEC1-encoded secp256k1 public key, recovering y from x and the
+    // parity prefix for the compressed form. Panics with a descriptive
+    // message on malformed input rather than an opaque slice-index panic.
+    pub fn parse_sec(sec_bin: &[u8]) -> Point {
+        let curve = crate::curve::secp256k1();
+
+        if sec_bin.is_empty() {
+            panic!("SEC input is empty");
+        }
+
+        if sec_bin[0] == 0x00 {
+            return Point::new(CurveElement::None, CurveElement::None, curve);
+        }
+
+        if sec_bin[0] == 0x04 {
+            if sec_bin.len() != 65 {
+                panic!("uncompressed SEC input must be 65 bytes, got {}", sec_bin.len());
+            }
+            let x = BigInt::from_bytes_be(Sign::Plus, &sec_bin[1..33]);
+            let y = BigInt::from_bytes_be(Sign::Plus, &sec_bin[33..65]);
+            return Point::new(CurveElement::Some(x), CurveElement::Some(y), curve);
+        }
+
+        if (sec_bin[0] != 0x02) && (sec_bin[0] != 0x03) {
+            panic!("unrecognized SEC prefix byte {:#04x}", sec_bin[0]);
+        }
+
+        if sec_bin.len() != 33 {
+            panic!("compressed SEC input must be 33 bytes, got {}", sec_bin.len());
+        }
+
+        let is_even = sec_bin[0] == 0x02;
+        let x = BigInt::from_bytes_be(Sign::Plus, &sec_bin[1..33]);
+        let p = curve.p.clone();
+
+        // y^2 = x^3 + ax + b (mod p)
+        let alpha = curve_element::modulo(&(pow(x.clone(), 3) + BigInt::from(curve.a) * &x + BigInt::from(curve.b)), &p);
+
+        // secp256k1's p ≡ 3 (mod 4), so sqrt(alpha) = alpha^((p+1)/4) mod p
+        let exponent = (&p + BigInt::one()) / BigInt::from(4);
+        let beta = alpha.modpow(&exponent, &p);
+
+        let even_beta = if (&beta % BigInt::from(2)) == BigInt::zero() { beta.clone() } else { &p - &beta };
+        let odd_beta = &p - &even_beta;
+
+        let y = if is_even { even_beta } else { odd_beta };
+
+        Point::new(CurveElement::Some(x), CurveElement::Some(y), curve)
+    }
+}
+
+// Implement == for 2 points
+impl PartialEq for Point {
+    fn eq(&self, other: &Self) -> bool {
+
+        if (self.x == other.x) & (self.y == other.y) & (self.curve == other.curve) {
+            true
+        }else {
+            false
+        }
+    }
+}
+impl Eq for Point {}
+
+// Implement scalar multiplication for a point via left-to-right double-and-add
+// k * P in O(log k) group operations instead of k - 1 additions
+impl Mul<u64> for Point {
+    type Output = Self;
+
+    fn mul(self, coefficient: u64) -> Self {
+        // Start from the point at infinity, carrying the curve across it so
+        // the "same curve" check in Add does not panic on the sentinel.
+        let mut result = Point{x: CurveElement::None, y: CurveElement::None, curve: self.curve.clone()};
+
+        for i in (0..64).rev() {
+            result = result.clone() + result;
+            if (coefficient >> i) & 1 == 1 {
+                result = result + self.clone();
+            }
+        }
+
+        result
+    }
+}
+
+// Implement scalar multiplication by an arbitrary-precision coefficient
+// (the u64 version above can't address secp256k1-sized scalars). The bit
+// length isn't known up front, so this walks right-to-left instead.
+impl Mul<BigInt> for Point {
+    type Output = Self;
+
+    fn mul(self, coefficient: BigInt) -> Self {
+        let mut result = Point{x: CurveElement::None, y: CurveElement::None, curve: self.curve.clone()};
+        let mut addend = self;
+        let mut k = coefficient;
+
+        while k > BigInt::zero() {
+            if &k % 2 != BigInt::zero() {
+                result = result + addend.clone();
+            }
+            addend = addend.clone() + addend;
+            k /= 2;
+        }
+
+        result
+    }
+}
+
+// Implement addition for 2 points
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        if self.curve != other.curve {
+            panic!("Points {:?}, {:?} are not on the same curve", self.view(), other.view())
+        }
+
+        // Case 0.0: self is the point at infinity, return other
+        if self.x.is_none() {
+            return other;
+        }
+
+        // Case 0.1: other is the point at infinity, return self
+        if other.x.is_none() {
+            return self;
+        }
+
+        // Case 1: self.x == other.x, self.y != other.y
+        // Result is point at infinity
+        if (self.x == other.x) & (self.y != other.y) {
+            return Point{x: CurveElement::None, y: CurveElement::None, curve: self.curve};
+        }
+
+        let curve = self.curve.clone();
+        let modulus = curve.p.clone();
+
+        // Case 2: self.x ≠ other.x
+        // Formula (x3,y3)==(x1,y1)+(x2,y2)
+        // s=(y2-y1)/(x2-x1)
+        // x3=s**2-x1-x2
+        // y3=s*(x1-x3)-y1
+
+        if self.x != other.x {
+            let sx = self.x.unwrap().clone();
+            let sy = self.y.unwrap().clone();
+            let ox = other.x.unwrap().clone();
+            let oy = other.y.unwrap().clone();
+
+            let inv = curve_element::mod_inverse(&(&ox - &sx), &modulus);
+            let s = curve_element::modulo(&((&oy - &sy) * inv), &modulus);
+            let x = curve_element::modulo(&(pow(s.clone(), 2) - &sx - &ox), &modulus);
+            let y = curve_element::modulo(&(&s * (&sx - &x) - &sy), &modulus);
+            return Point{x: CurveElement::Some(x), y: CurveElement::Some(y), curve: curve};
+        }
+
+        // Case 4: if we are tangent to the vertical line,
+        // we return the point at infinity
+        if (self == other) & self.y.unwrap().is_zero() {
+            return Point{x: CurveElement::None, y: CurveElement::None, curve: curve};
+        }
+
+        // Case 3: self == other
+        // Formula (x3,y3)=(x1,y1)+(x1,y1)
+        // s=(3*x1**2+a)/(2*y1)
+        // x3=s**2-2*x1
+        // y3=s*(x1-x3)-y1
+        if self == other {
+            let sx = self.x.unwrap().clone();
+            let sy = self.y.unwrap().clone();
+
+            let inv = curve_element::mod_inverse(&(BigInt::from(2) * &sy), &modulus);
+            let s = curve_element::modulo(&((BigInt::from(3) * pow(sx.clone(), 2) + BigInt::from(curve.a)) * inv), &modulus);
+            let x = curve_element::modulo(&(pow(s.clone(), 2) - BigInt::from(2) * &sx), &modulus);
+            let y = curve_element::modulo(&(&s * (&sx - &x) - &sy), &modulus);
+            return Point{x: CurveElement::Some(x), y: CurveElement::Some(y), curve: curve};
+        }
+        panic!("Something wrong with the addition");
+    }
+}
+
+// Implement negation: (x, y) -> (x, -y), the point at infinity maps to itself
+impl Neg for Point {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        if self.x.is_none() {
+            return self;
+        }
+
+        let y = curve_element::modulo(&(-self.y.unwrap()), &self.curve.p);
+        Point{x: self.x, y: CurveElement::Some(y), curve: self.curve}
+    }
+}
+
+// Implement subtraction as P - Q == P + (-Q)
+impl Sub for Point {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + (-other)
+    }
+}
+
+
+
+//====================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A comfortably large prime so the small test fixtures below behave
+    // just as they did over plain integers (no values wrap around). The
+    // generator/order/cofactor are unused by these tests, so they're given
+    // placeholder values (G = (0, 0), n = 1, h = 1).
+    fn test_curve() -> Curve {
+        Curve::new(5, 7, BigInt::from(1_000_000_007i64), BigInt::zero(), BigInt::zero(), BigInt::one(), 1)
+    }
+
+    #[test]
+
+    fn valid_point() {
+
+        let i2c = CurveElement::int_to_curve;
+        let curve = test_curve();
+
+        // On curve
+        let _x = Point::new(i2c(3), i2c(-7), curve.clone()); // should not raise an error
+        let _x = Point::new(i2c(18), i2c(77), curve.clone()); // should not raise an error
+
+        // == implementation
+        let p1 = Point::new(i2c(3), i2c(-7), curve.clone());
+        let p2 = Point::new(i2c(3), i2c(-7), curve.clone());
+        let p3 = Point::new(i2c(18), i2c(77), curve.clone());
+        assert!(p1 == p2);
+        assert!(p1 != p3);
+
+        // Show point
+        assert_eq!(p1.view(), String::from("Point(3,1000000000)_5_7")); // -7 mod p, stored canonically
+
+        // Test add 0
+        let a = Point::new(CurveElement::None, CurveElement::None, curve.clone());
+        let b = Point::new(i2c(2), i2c(5), curve.clone());
+        let c = Point::new(i2c(2), i2c(-5), curve.clone());
+        assert_eq!(a.clone() + b.clone(), b);
+        assert_eq!(b.clone() + a.clone(), b);
+        assert_eq!(b + c, a);
+
+        // Test add 1
+        let a = Point::new(i2c(3), i2c(7), curve.clone());
+        let b = Point::new(i2c(-1), i2c(-1), curve.clone());
+        assert_eq!(a + b, Point::new(i2c(2), i2c(-5), curve.clone()));
+
+        // Test add2
+        let a = Point::new(i2c(-1), i2c(1), curve.clone());
+        assert_eq!(a.clone() + a, Point::new(i2c(18), i2c(-77), curve));
+
+    }
+
+    #[test]
+    fn scalar_multiplication() {
+
+        let i2c = CurveElement::int_to_curve;
+        let curve = test_curve();
+
+        let p = Point::new(i2c(-1), i2c(-1), curve.clone());
+        let inf = Point::new(CurveElement::None, CurveElement::None, curve);
+
+        // 0 * P is the point at infinity
+        let zero: u64 = 0;
+        assert_eq!(p.clone() * zero, inf);
+
+        // 1 * P is P itself
+        assert_eq!(p.clone() * 1, p);
+
+        // 2 * P matches manual doubling via Add
+        assert_eq!(p.clone() * 2, p.clone() + p.clone());
+
+        // 3 * P matches manual addition via Add
+        assert_eq!(p.clone() * 3, p.clone() + p.clone() + p);
+    }
+
+    #[test]
+    fn scalar_multiplication_bigint() {
+
+        let i2c = CurveElement::int_to_curve;
+        let curve = test_curve();
+
+        let p = Point::new(i2c(-1), i2c(-1), curve.clone());
+        let inf = Point::new(CurveElement::None, CurveElement::None, curve);
+
+        // BigInt and u64 scalar multiplication must agree
+        assert_eq!(p.clone() * BigInt::from(0), inf);
+        assert_eq!(p.clone() * BigInt::from(1), p.clone() * 1);
+        assert_eq!(p.clone() * BigInt::from(2), p.clone() * 2);
+        assert_eq!(p.clone() * BigInt::from(5), p.clone() * 5);
+    }
+
+    #[test]
+    fn negation_and_subtraction() {
+
+        let i2c = CurveElement::int_to_curve;
+        let curve = test_curve();
+
+        let a = Point::new(i2c(-1), i2c(-1), curve.clone());
+        let b = Point::new(i2c(-1), i2c(1), curve.clone());
+        let inf = Point::new(CurveElement::None, CurveElement::None, curve);
+
+        // -P negates y and leaves x untouched
+        assert_eq!(-a.clone(), b);
+
+        // The point at infinity negates to itself
+        assert_eq!(-inf.clone(), inf);
+
+        // P + (-P) == infinity, same as the existing "opposite y" Add case
+        assert_eq!(a.clone() + (-a.clone()), inf);
+
+        // P - P == infinity
+        assert_eq!(a.clone() - a, inf);
+    }
+
+    #[test]
+    fn sec_serialization() {
+
+        let curve = crate::curve::secp256k1();
+
+        // secp256k1 generator point G, a well-known on-curve test vector
+        let gx = BigInt::parse_bytes(b"79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798", 16).unwrap();
+        let gy = BigInt::parse_bytes(b"483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8", 16).unwrap();
+
+        let g = Point::new(CurveElement::Some(gx), CurveElement::Some(gy), curve.clone());
+
+        // Uncompressed round-trip
+        let uncompressed = g.to_sec(false);
+        assert_eq!(uncompressed.len(), 65);
+        assert_eq!(uncompressed[0], 0x04);
+        assert_eq!(Point::parse_sec(&uncompressed), g);
+
+        // Compressed round-trip
+        let compressed = g.to_sec(true);
+        assert_eq!(compressed.len(), 33);
+        assert_eq!(compressed[0], 0x02); // G's y is even
+        assert_eq!(Point::parse_sec(&compressed), g);
+
+        // Point at infinity
+        let inf = Point::new(CurveElement::None, CurveElement::None, curve);
+        assert_eq!(inf.to_sec(false), vec![0x00]);
+        assert_eq!(Point::parse_sec(&[0x00]), inf);
+    }
+
+    #[test]
+    #[should_panic(expected = "empty")]
+    fn parse_sec_rejects_empty_input() {
+        Point::parse_sec(&[]);
+    }
+
+    #[test]
+    #[should_panic(expected = "uncompressed SEC input must be 65 bytes")]
+    fn parse_sec_rejects_short_uncompressed_input() {
+        Point::parse_sec(&[0x04, 0x01, 0x02]);
+    }
+
+    #[test]
+    #[should_panic(expected = "compressed SEC input must be 33 bytes")]
+    fn parse_sec_rejects_short_compressed_input() {
+        Point::parse_sec(&[0x02, 0x01, 0x02]);
+    }
+
+    #[test]
+    #[should_panic(expected = "unrecognized SEC prefix byte")]
+    fn parse_sec_rejects_unknown_prefix() {
+        Point::parse_sec(&[0xFF; 33]);
+    }
+}
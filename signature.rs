@@ -0,0 +1,73 @@
+use num_bigint::BigInt;
+use crate::curve;
+use crate::curve_element;
+use crate::point::Point;
+
+// An ECDSA signature over secp256k1
+#[derive(Debug, Clone, PartialEq)]
+pub struct Signature {
+    pub r: BigInt,
+    pub s: BigInt,
+}
+
+// Sign hash `z` with private key `secret`, using per-signature nonce `k`.
+// r is the x-coordinate of k*G reduced mod n; s = (z + r*secret) * k^-1 mod n.
+pub fn sign(secret: BigInt, z: BigInt, k: BigInt) -> Signature {
+    let curve = curve::secp256k1();
+    let n = curve.n.clone();
+
+    let kg = curve.generator() * k.clone();
+    let r = curve_element::modulo(kg.x.unwrap(), &n);
+    let k_inv = curve_element::mod_inverse(&k, &n);
+    let s = curve_element::modulo(&((&z + &r * &secret) * k_inv), &n);
+
+    Signature{r, s}
+}
+
+// Verify that `sig` is a valid ECDSA signature of hash `z` under `pubkey`.
+// u = z*s^-1 mod n, v = r*s^-1 mod n; valid iff x-coordinate of u*G + v*pubkey == r mod n.
+pub fn verify(pubkey: Point, z: BigInt, sig: &Signature) -> bool {
+    let curve = curve::secp256k1();
+    let n = curve.n.clone();
+
+    let s_inv = curve_element::mod_inverse(&sig.s, &n);
+    let u = curve_element::modulo(&(&z * &s_inv), &n);
+    let v = curve_element::modulo(&(&sig.r * &s_inv), &n);
+
+    let total = curve.generator() * u + pubkey * v;
+
+    if total.x.is_none() {
+        return false;
+    }
+
+    curve_element::modulo(total.x.unwrap(), &n) == curve_element::modulo(&sig.r, &n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_and_verify_roundtrip() {
+        let secret = BigInt::from(12345);
+        let z = BigInt::from(987654321u64);
+        let k = BigInt::from(1234567890123u64);
+
+        let sig = sign(secret.clone(), z.clone(), k);
+        let pubkey = curve::secp256k1().generator() * secret;
+
+        assert!(verify(pubkey, z, &sig));
+    }
+
+    #[test]
+    fn verify_rejects_wrong_hash() {
+        let secret = BigInt::from(12345);
+        let z = BigInt::from(987654321u64);
+        let k = BigInt::from(1234567890123u64);
+
+        let sig = sign(secret.clone(), z, k);
+        let pubkey = curve::secp256k1().generator() * secret;
+
+        assert!(!verify(pubkey, BigInt::from(1), &sig));
+    }
+}